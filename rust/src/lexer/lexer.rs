@@ -1,9 +1,73 @@
-use anyhow::Result;
+/// A byte-offset range (`[start, end)`) into the lexer's input, identifying
+/// where a token was scanned from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// The category of problem a [`LexError`] represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LexErrorKind {
+    UnexpectedChar,
+    UnterminatedString,
+    InvalidNumber,
+    InvalidUtf8,
+}
+
+/// A problem encountered while scanning a token. The lexer never panics;
+/// malformed input is reported through this type instead, and scanning can
+/// continue afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexError<'src> {
+    pub kind: LexErrorKind,
+    pub byte: u8,
+    pub span: Span,
+    source: &'src [u8],
+}
+
+impl<'src> LexError<'src> {
+    fn line_col(&self) -> (usize, usize) {
+        let mut line = 1;
+        let mut col = 1;
+
+        for &b in self.source.iter().take(self.span.start) {
+            if b == b'\n' {
+                line += 1;
+                col = 1;
+            } else {
+                col += 1;
+            }
+        }
+
+        return (line, col);
+    }
+}
+
+impl<'src> std::fmt::Display for LexError<'src> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (line, col) = self.line_col();
+
+        return match self.kind {
+            LexErrorKind::UnexpectedChar => {
+                write!(f, "{}:{}: unexpected character '{}'", line, col, self.byte as char)
+            }
+            LexErrorKind::UnterminatedString => write!(f, "{}:{}: unterminated string or char literal", line, col),
+            LexErrorKind::InvalidNumber => write!(f, "{}:{}: invalid numeric literal", line, col),
+            LexErrorKind::InvalidUtf8 => write!(f, "{}:{}: invalid utf-8 in literal", line, col),
+        };
+    }
+}
+
+impl<'src> std::error::Error for LexError<'src> {}
 
 #[derive(Debug, PartialEq)]
-pub enum Token {
-    Ident(String),
-    Int(String),
+pub enum Token<'src> {
+    Ident(&'src str),
+    Int(&'src str),
+    Float(&'src str),
+    String(String),
+    CharLit(String),
 
     Illegal,
     Eof,
@@ -85,29 +149,107 @@ pub enum Token {
     Minus,
 }
 
-pub struct Lexer {
+/// Declares the operator tokens' shared metadata in one place: the literal
+/// they scan from, their binding power as an infix operator (if any), and
+/// their `=`-compound-assignment partner (if any). `Token::precedence`,
+/// `Token::is_binary_op`, and `Token::assign_variant` are all generated
+/// from this single table so the lexer's multi-char matching and the
+/// parser's precedence climbing never fall out of sync with each other.
+macro_rules! operator_table {
+    ($($variant:ident => { literal: $literal:literal, prec: $prec:expr, assign: $assign:expr }),* $(,)?) => {
+        impl<'src> Token<'src> {
+            /// The source text this operator token always scans from.
+            pub fn literal(&self) -> Option<&'static str> {
+                match self {
+                    $(Token::$variant => Some($literal),)*
+                    _ => None,
+                }
+            }
+
+            /// Binding power for use as an infix operator in a Pratt
+            /// parser, or `None` if this token is never a binary operator.
+            pub fn precedence(&self) -> Option<u8> {
+                match self {
+                    $(Token::$variant => $prec,)*
+                    _ => None,
+                }
+            }
+
+            pub fn is_binary_op(&self) -> bool {
+                self.precedence().is_some()
+            }
+
+            /// The compound-assignment form of this operator (`Plus` ->
+            /// `PlusEqual`), if one exists.
+            pub fn assign_variant(&self) -> Option<Token<'src>> {
+                match self {
+                    $(Token::$variant => $assign,)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+operator_table! {
+    Plus => { literal: "+", prec: Some(6), assign: Some(Token::PlusEqual) },
+    PlusEqual => { literal: "+=", prec: None, assign: None },
+    Minus => { literal: "-", prec: Some(6), assign: Some(Token::MinusEqual) },
+    MinusEqual => { literal: "-=", prec: None, assign: None },
+    Ampersand => { literal: "&", prec: Some(4), assign: None },
+    AmpersandAmpersand => { literal: "&&", prec: Some(2), assign: None },
+    Pipe => { literal: "|", prec: Some(3), assign: None },
+    PipePipe => { literal: "||", prec: Some(1), assign: None },
+    Bang => { literal: "!", prec: None, assign: None },
+    BangEqual => { literal: "!=", prec: Some(5), assign: None },
+    Equal => { literal: "=", prec: None, assign: None },
+    EqualEqual => { literal: "==", prec: Some(5), assign: None },
+    Period => { literal: ".", prec: Some(8), assign: None },
+    DoubleColon => { literal: "::", prec: Some(8), assign: None },
+    Lparen => { literal: "(", prec: Some(9), assign: None },
+    Arrow => { literal: "->", prec: None, assign: None },
+}
+
+pub struct Lexer<'src> {
     position: usize,
     read_position: usize,
     ch: u8,
-    input: Vec<u8>,
+    input: &'src [u8],
 }
 
-impl Lexer {
-    fn new(input: String) -> Lexer {
+impl<'src> Lexer<'src> {
+    pub fn new(input: &'src str) -> Lexer<'src> {
         let mut lex = Lexer {
             position: 0,
             read_position: 0,
             ch: 0,
-            input: input.into_bytes(),
+            input: input.as_bytes(),
         };
         lex.read_char();
 
         return lex;
     }
 
-    pub fn next_token(&mut self) -> Result<Token> {
+    pub fn next_token(&mut self) -> std::result::Result<(Token<'src>, Span), LexError<'src>> {
         self.skip_whitespace();
 
+        let start = self.position;
+        let tok = self.read_token(start)?;
+        let end = self.position;
+
+        return Ok((tok, Span { start, end }));
+    }
+
+    fn error(&self, kind: LexErrorKind, start: usize) -> LexError<'src> {
+        return LexError {
+            kind,
+            byte: self.ch,
+            span: Span { start, end: self.position },
+            source: self.input,
+        };
+    }
+
+    fn read_token(&mut self, start: usize) -> std::result::Result<Token<'src>, LexError<'src>> {
         let tok = match self.ch {
             b'{' => Token::LSquirly,
             b'}' => Token::RSquirly,
@@ -117,15 +259,47 @@ impl Lexer {
             b';' => Token::Semicolon,
             b'+' => {
                 let ident = self.read_match_any(&[b'+', b'=']);
-                return Ok(match ident.as_str() {
-                    "+=" => Token::PlusEqual,
-                    _ => Token::Plus,
+                return Ok(if Token::PlusEqual.literal() == Some(ident) {
+                    Token::Plus.assign_variant().unwrap()
+                } else {
+                    Token::Plus
+                });
+            }
+            b'=' => {
+                let ident = self.read_match_any(&[b'=']);
+                return Ok(if Token::EqualEqual.literal() == Some(ident) {
+                    Token::EqualEqual
+                } else {
+                    Token::Equal
+                });
+            }
+            b'&' => {
+                let ident = self.read_match_any(&[b'&']);
+                return Ok(if Token::AmpersandAmpersand.literal() == Some(ident) {
+                    Token::AmpersandAmpersand
+                } else {
+                    Token::Ampersand
+                });
+            }
+            b'|' => {
+                let ident = self.read_match_any(&[b'|']);
+                return Ok(if Token::PipePipe.literal() == Some(ident) {
+                    Token::PipePipe
+                } else {
+                    Token::Pipe
+                });
+            }
+            b'!' => {
+                let ident = self.read_match_any(&[b'!', b'=']);
+                return Ok(if Token::BangEqual.literal() == Some(ident) {
+                    Token::BangEqual
+                } else {
+                    Token::Bang
                 });
             }
-            b'=' => Token::Equal,
             b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
                 let ident = self.read_ident();
-                return Ok(match ident.as_str() {
+                return Ok(match ident {
                     "fn" => Token::Function,
                     "let" => Token::Let,
                     "const" => Token::Const,
@@ -180,34 +354,42 @@ impl Lexer {
                 });
             }
 
-            b'0'..=b'9' => return Ok(Token::Int(self.read_int())),
+            b'0'..=b'9' => return self.read_number(start),
+            b'"' => return Ok(Token::String(self.read_string(start)?)),
+            b'\'' => return Ok(Token::CharLit(self.read_char_literal(start)?)),
             0 => Token::Eof,
             b'.' => {
                 let ident = self.read_match_any(&[b'.', b'=']);
-                return Ok(match ident.as_str() {
+                return Ok(match ident {
                     "." => Token::Period,
                     ".." => Token::Range,
                     "..=" => Token::RangeInclusive,
                     "..." => Token::DefaultFields,
-                    _ => todo!("we need to implement this....1"),
+                    _ => return Err(self.error(LexErrorKind::UnexpectedChar, start)),
                 });
             }
             b':' => {
                 let ident = self.read_match(b':');
-                return Ok(match ident.as_str() {
+                return Ok(match ident {
                     "::" => Token::DoubleColon,
                     _ => Token::Colon,
                 });
             }
             b'-' => {
                 let ident = self.read_match_any(&[b'-', b'=', b'>']);
-                return Ok(match ident.as_str() {
-                    "-=" => Token::MinusEqual,
-                    "->" => Token::Arrow,
-                    _ => Token::Minus,
+                return Ok(if Token::MinusEqual.literal() == Some(ident) {
+                    Token::Minus.assign_variant().unwrap()
+                } else if Token::Arrow.literal() == Some(ident) {
+                    Token::Arrow
+                } else {
+                    Token::Minus
                 });
             }
-            _ => todo!("we need to implement this....2"),
+            _ => {
+                let err = self.error(LexErrorKind::UnexpectedChar, start);
+                self.read_char(); // resynchronize past the offending byte
+                return Err(err);
+            }
         };
 
         self.read_char();
@@ -231,7 +413,7 @@ impl Lexer {
         }
     }
 
-    fn read_ident(&mut self) -> String {
+    fn read_ident(&mut self) -> &'src str {
         let pos = self.position;
         // fist char must be a letter or underscore
         if self.ch.is_ascii_alphabetic() || self.ch == b'_' {
@@ -242,34 +424,248 @@ impl Lexer {
             }
         }
 
-        return String::from_utf8_lossy(&self.input[pos..self.position]).to_string();
+        return Self::slice(self.input, pos, self.position);
     }
 
-    fn read_match(&mut self, match_byte: u8) -> String {
+    fn read_match(&mut self, match_byte: u8) -> &'src str {
         let pos = self.position;
         while self.ch == match_byte {
             self.read_char();
         }
 
-        return String::from_utf8_lossy(&self.input[pos..self.position]).to_string();
+        return Self::slice(self.input, pos, self.position);
     }
 
-    fn read_match_any(&mut self, match_bytes: &[u8]) -> String {
+    fn read_match_any(&mut self, match_bytes: &[u8]) -> &'src str {
         let pos = self.position;
         while match_bytes.contains(&self.ch) {
             self.read_char();
         }
 
-        return String::from_utf8_lossy(&self.input[pos..self.position]).to_string();
+        return Self::slice(self.input, pos, self.position);
     }
 
-    fn read_int(&mut self) -> String {
+    fn peek_byte(&self) -> u8 {
+        self.input.get(self.read_position).copied().unwrap_or(0)
+    }
+
+    fn read_number(&mut self, start: usize) -> std::result::Result<Token<'src>, LexError<'src>> {
         let pos = self.position;
+
+        // hex/binary/octal literals: 0x.., 0b.., 0o..
+        if self.ch == b'0' && matches!(self.peek_byte(), b'x' | b'b' | b'o') {
+            let base = self.peek_byte();
+            self.read_char();
+            self.read_char();
+
+            let digits_start = self.position;
+            while self.ch.is_ascii_alphanumeric() {
+                self.read_char();
+            }
+            let digits = Self::slice(self.input, digits_start, self.position);
+
+            let is_valid_digit: fn(u8) -> bool = match base {
+                b'x' => |b| b.is_ascii_hexdigit(),
+                b'b' => |b| matches!(b, b'0' | b'1'),
+                b'o' => |b| matches!(b, b'0'..=b'7'),
+                _ => unreachable!(),
+            };
+
+            if digits.is_empty() || !digits.bytes().all(is_valid_digit) {
+                return Err(self.error(LexErrorKind::InvalidNumber, start));
+            }
+
+            return Ok(Token::Int(Self::slice(self.input, pos, self.position)));
+        }
+
         while self.ch.is_ascii_digit() {
             self.read_char();
         }
 
-        return String::from_utf8_lossy(&self.input[pos..self.position]).to_string();
+        let mut is_float = false;
+
+        if self.ch == b'.' && self.peek_byte().is_ascii_digit() {
+            is_float = true;
+            self.read_char();
+            while self.ch.is_ascii_digit() {
+                self.read_char();
+            }
+        }
+
+        if (self.ch == b'e' || self.ch == b'E') && self.exponent_follows() {
+            is_float = true;
+            self.read_char();
+            if self.ch == b'+' || self.ch == b'-' {
+                self.read_char();
+            }
+            while self.ch.is_ascii_digit() {
+                self.read_char();
+            }
+        }
+
+        let text = Self::slice(self.input, pos, self.position);
+
+        return Ok(if is_float {
+            Token::Float(text)
+        } else {
+            Token::Int(text)
+        });
+    }
+
+    /// Whether `self.ch` (an `e`/`E`) is actually the start of an exponent,
+    /// i.e. followed by digits or a sign and then digits.
+    fn exponent_follows(&self) -> bool {
+        let mut pos = self.read_position;
+        if matches!(self.input.get(pos), Some(b'+') | Some(b'-')) {
+            pos += 1;
+        }
+
+        return self.input.get(pos).is_some_and(u8::is_ascii_digit);
+    }
+
+    fn read_string(&mut self, start: usize) -> std::result::Result<String, LexError<'src>> {
+        let mut bytes: Vec<u8> = Vec::new();
+        self.read_char(); // consume opening "
+
+        loop {
+            match self.ch {
+                0 => return Err(self.error(LexErrorKind::UnterminatedString, start)),
+                b'"' => {
+                    self.read_char();
+                    return String::from_utf8(bytes).map_err(|_| self.error(LexErrorKind::InvalidUtf8, start));
+                }
+                b'\\' => {
+                    self.read_char();
+                    self.read_escape(start, &mut bytes)?;
+                }
+                ch => {
+                    bytes.push(ch);
+                    self.read_char();
+                }
+            }
+        }
+    }
+
+    fn read_char_literal(&mut self, start: usize) -> std::result::Result<String, LexError<'src>> {
+        let mut bytes: Vec<u8> = Vec::new();
+        self.read_char(); // consume opening '
+
+        match self.ch {
+            0 => return Err(self.error(LexErrorKind::UnterminatedString, start)),
+            b'\\' => {
+                self.read_char();
+                self.read_escape(start, &mut bytes)?;
+            }
+            ch => {
+                bytes.push(ch);
+                self.read_char();
+            }
+        }
+
+        if self.ch != b'\'' {
+            return Err(self.error(LexErrorKind::UnterminatedString, start));
+        }
+        self.read_char(); // consume closing '
+
+        return String::from_utf8(bytes).map_err(|_| self.error(LexErrorKind::InvalidUtf8, start));
+    }
+
+    /// Decodes the escape sequence starting right after the backslash
+    /// (`self.ch` is the character following `\`) and appends its UTF-8
+    /// bytes to `out`.
+    fn read_escape(&mut self, start: usize, out: &mut Vec<u8>) -> std::result::Result<(), LexError<'src>> {
+        let decoded = match self.ch {
+            b'n' => '\n',
+            b't' => '\t',
+            b'r' => '\r',
+            b'0' => '\0',
+            b'\\' => '\\',
+            b'"' => '"',
+            b'\'' => '\'',
+            b'u' => return self.read_unicode_escape(start, out),
+            _ => return Err(self.error(LexErrorKind::UnexpectedChar, start)),
+        };
+
+        self.read_char();
+        let mut buf = [0u8; 4];
+        out.extend_from_slice(decoded.encode_utf8(&mut buf).as_bytes());
+
+        return Ok(());
+    }
+
+    fn read_unicode_escape(&mut self, start: usize, out: &mut Vec<u8>) -> std::result::Result<(), LexError<'src>> {
+        self.read_char(); // consume 'u'
+        if self.ch != b'{' {
+            return Err(self.error(LexErrorKind::UnexpectedChar, start));
+        }
+        self.read_char(); // consume '{'
+
+        let pos = self.position;
+        while self.ch != b'}' {
+            if self.ch == 0 {
+                return Err(self.error(LexErrorKind::UnterminatedString, start));
+            }
+            self.read_char();
+        }
+
+        let hex = Self::slice(self.input, pos, self.position);
+        let code = u32::from_str_radix(hex, 16).map_err(|_| self.error(LexErrorKind::UnexpectedChar, start))?;
+        let ch = char::from_u32(code).ok_or_else(|| self.error(LexErrorKind::UnexpectedChar, start))?;
+
+        self.read_char(); // consume '}'
+
+        let mut buf = [0u8; 4];
+        out.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+
+        return Ok(());
+    }
+
+    /// All of the byte ranges we slice out of `input` are delimited by ASCII
+    /// bytes (digits, letters, `_`, and the punctuation matched in
+    /// `read_token`), so the slice is always valid UTF-8.
+    fn slice(input: &'src [u8], start: usize, end: usize) -> &'src str {
+        std::str::from_utf8(&input[start..end]).expect("token span is not valid utf8")
+    }
+}
+
+/// Lexes the entire `input` up front, returning every successfully scanned
+/// `(Token, Span)` pair through `Token::Eof`, plus every [`LexError`]
+/// encountered along the way, in the order they occurred. The lexer
+/// resynchronizes after each error, so one malformed token doesn't stop the
+/// rest of `input` from being scanned.
+pub fn lex(input: &str) -> (Vec<(Token<'_>, Span)>, Vec<LexError<'_>>) {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    let mut errors = Vec::new();
+
+    loop {
+        match lexer.next_token() {
+            Ok((Token::Eof, span)) => {
+                tokens.push((Token::Eof, span));
+                return (tokens, errors);
+            }
+            Ok(tok) => tokens.push(tok),
+            Err(err) => errors.push(err),
+        }
+    }
+}
+
+impl<'src> Iterator for Lexer<'src> {
+    type Item = std::result::Result<(Token<'src>, Span), LexError<'src>>;
+
+    /// Stops (returns `None`) at `Token::Eof`; a lex error is yielded as
+    /// `Some(Err(_))` rather than being conflated with running out of
+    /// input, so callers like `Parser` can tell "no more tokens" apart
+    /// from "the lexer choked". The lexer keeps going after an error: the
+    /// next call resumes scanning where the failed token stopped (past an
+    /// unexpected byte, but at the offending byte of a bad escape or char
+    /// literal).
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_token() {
+            Ok((Token::Eof, _)) => None,
+            Ok(item) => Some(Ok(item)),
+            Err(err) => Some(Err(err)),
+        }
     }
 }
 
@@ -282,7 +678,7 @@ mod test {
     #[test]
     fn get_next_token() -> Result<()> {
         let input = "=+(){},;";
-        let mut lexer = Lexer::new(input.into());
+        let mut lexer = Lexer::new(input);
 
         let tokens = vec![
             Token::Equal,
@@ -296,7 +692,7 @@ mod test {
         ];
 
         for token in tokens {
-            let next_token = lexer.next_token()?;
+            let (next_token, _) = lexer.next_token()?;
             println!("expected: {:?}, received {:?}", token, next_token);
             assert_eq!(token, next_token);
         }
@@ -313,50 +709,50 @@ mod test {
             };
             let result = add(five, ten);"#;
 
-        let mut lex = Lexer::new(input.into());
+        let mut lex = Lexer::new(input);
 
         let tokens = vec![
             Token::Let,
-            Token::Ident(String::from("five")),
+            Token::Ident("five"),
             Token::Equal,
-            Token::Int(String::from("5")),
+            Token::Int("5"),
             Token::Semicolon,
             Token::Let,
-            Token::Ident(String::from("ten")),
+            Token::Ident("ten"),
             Token::Equal,
-            Token::Int(String::from("10")),
+            Token::Int("10"),
             Token::Semicolon,
             Token::Let,
-            Token::Ident(String::from("add")),
+            Token::Ident("add"),
             Token::Equal,
             Token::Function,
             Token::Lparen,
-            Token::Ident(String::from("x")),
+            Token::Ident("x"),
             Token::Comma,
-            Token::Ident(String::from("y")),
+            Token::Ident("y"),
             Token::Rparen,
             Token::LSquirly,
-            Token::Ident(String::from("x")),
+            Token::Ident("x"),
             Token::Plus,
-            Token::Ident(String::from("y")),
+            Token::Ident("y"),
             Token::Semicolon,
             Token::RSquirly,
             Token::Semicolon,
             Token::Let,
-            Token::Ident(String::from("result")),
+            Token::Ident("result"),
             Token::Equal,
-            Token::Ident(String::from("add")),
+            Token::Ident("add"),
             Token::Lparen,
-            Token::Ident(String::from("five")),
+            Token::Ident("five"),
             Token::Comma,
-            Token::Ident(String::from("ten")),
+            Token::Ident("ten"),
             Token::Rparen,
             Token::Semicolon,
             Token::Eof,
         ];
 
         for token in tokens {
-            let next_token = lex.next_token()?;
+            let (next_token, _) = lex.next_token()?;
             println!("expected: {:?}, received {:?}", token, next_token);
             assert_eq!(token, next_token);
         }
@@ -389,93 +785,300 @@ mod test {
             }            
             "#;
 
-        let mut lex = Lexer::new(input.into());
+        let mut lex = Lexer::new(input);
 
         let tokens = vec![
             Token::Pub,
             Token::Mod,
-            Token::Ident(String::from("nutz")),
+            Token::Ident("nutz"),
             Token::LSquirly,
             Token::Use,
             Token::Crate,
             Token::DoubleColon,
-            Token::Ident(String::from("both_nutz")),
+            Token::Ident("both_nutz"),
             Token::Semicolon,
             Token::Pub,
             Token::Const,
-            Token::Ident(String::from("NUTZ")),
+            Token::Ident("NUTZ"),
             Token::Colon,
             Token::U32,
             Token::Equal,
-            Token::Int(String::from("5")),
+            Token::Int("5"),
             Token::Semicolon,
             Token::Pub,
             Token::Static,
-            Token::Ident(String::from("NUTZ2")),
+            Token::Ident("NUTZ2"),
             Token::Colon,
             Token::U32,
             Token::Equal,
-            Token::Int(String::from("10")),
+            Token::Int("10"),
             Token::Semicolon,
             Token::Function,
-            Token::Ident(String::from("bunch_o_nutz")),
+            Token::Ident("bunch_o_nutz"),
             Token::Lparen,
             Token::Rparen,
             Token::Arrow,
             Token::U32,
             Token::LSquirly,
             Token::Let,
-            Token::Ident(String::from("rng1")),
+            Token::Ident("rng1"),
             Token::Equal,
-            Token::Ident(String::from("NUTZ")),
+            Token::Ident("NUTZ"),
             Token::Range,
-            Token::Ident(String::from("NUTZ2")),
+            Token::Ident("NUTZ2"),
             Token::Semicolon,
             Token::Let,
-            Token::Ident(String::from("rng2")),
+            Token::Ident("rng2"),
             Token::Equal,
-            Token::Ident(String::from("NUTZ")),
+            Token::Ident("NUTZ"),
             Token::RangeInclusive,
-            Token::Ident(String::from("NUTZ2")),
+            Token::Ident("NUTZ2"),
             Token::Semicolon,
             Token::Let,
             Token::Mut,
-            Token::Ident(String::from("x")),
+            Token::Ident("x"),
             Token::Equal,
-            Token::Int(String::from("0")),
+            Token::Int("0"),
             Token::Semicolon,
             Token::For,
-            Token::Ident(String::from("i")),
+            Token::Ident("i"),
             Token::In,
-            Token::Ident(String::from("rng1")),
+            Token::Ident("rng1"),
             Token::LSquirly,
-            Token::Ident(String::from("x")),
+            Token::Ident("x"),
             Token::PlusEqual,
-            Token::Ident(String::from("i")),
+            Token::Ident("i"),
             Token::Semicolon,
             Token::RSquirly,
             Token::For,
-            Token::Ident(String::from("i")),
+            Token::Ident("i"),
             Token::In,
-            Token::Ident(String::from("rng2")),
+            Token::Ident("rng2"),
             Token::LSquirly,
-            Token::Ident(String::from("x")),
+            Token::Ident("x"),
             Token::PlusEqual,
-            Token::Ident(String::from("i")),
+            Token::Ident("i"),
             Token::Semicolon,
             Token::RSquirly,
-            Token::Ident(String::from("i")),
+            Token::Ident("i"),
             Token::RSquirly,
             Token::RSquirly,
             Token::Eof,
         ];
 
         for token in tokens {
-            let next_token = lex.next_token()?;
+            let (next_token, _) = lex.next_token()?;
+            println!("expected: {:?}, received {:?}", token, next_token);
+            assert_eq!(token, next_token);
+        }
+
+        return Ok(());
+    }
+
+    #[test]
+    fn get_literals() -> Result<()> {
+        let input = r#""hello\nworld" 'a' '\u{1F600}' 3.14 6.02e23 1e-3 0x1F 0b101 0o17 42"#;
+
+        let mut lex = Lexer::new(input);
+
+        let tokens = vec![
+            Token::String(String::from("hello\nworld")),
+            Token::CharLit(String::from("a")),
+            Token::CharLit(String::from("\u{1F600}")),
+            Token::Float("3.14"),
+            Token::Float("6.02e23"),
+            Token::Float("1e-3"),
+            Token::Int("0x1F"),
+            Token::Int("0b101"),
+            Token::Int("0o17"),
+            Token::Int("42"),
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            let (next_token, _) = lex.next_token()?;
             println!("expected: {:?}, received {:?}", token, next_token);
             assert_eq!(token, next_token);
         }
 
         return Ok(());
     }
+
+    #[test]
+    fn invalid_digit_for_numeric_base_is_an_error() {
+        use super::LexErrorKind;
+
+        for input in ["0xZZ", "0b999", "0o99", "0x", "0b", "0o"] {
+            let mut lex = Lexer::new(input);
+            let err = lex.next_token().unwrap_err();
+            assert_eq!(err.kind, LexErrorKind::InvalidNumber, "input: {input}");
+        }
+    }
+
+    #[test]
+    fn lex_error_does_not_panic_and_resynchronizes() {
+        use super::{LexError, LexErrorKind};
+
+        let input = "x = @ 1;";
+        let mut lex = Lexer::new(input);
+
+        assert_eq!(lex.next_token().unwrap().0, Token::Ident("x"));
+        assert_eq!(lex.next_token().unwrap().0, Token::Equal);
+
+        let err: LexError = lex.next_token().unwrap_err();
+        assert_eq!(err.kind, LexErrorKind::UnexpectedChar);
+        assert_eq!(err.byte, b'@');
+        assert_eq!(format!("{}", err), "1:5: unexpected character '@'");
+
+        // the lexer resynchronized past the bad byte and kept going
+        assert_eq!(lex.next_token().unwrap().0, Token::Int("1"));
+        assert_eq!(lex.next_token().unwrap().0, Token::Semicolon);
+    }
+
+    #[test]
+    fn unterminated_string_is_an_error_not_a_panic() {
+        use super::LexErrorKind;
+
+        let input = r#""unterminated"#;
+        let mut lex = Lexer::new(input);
+
+        let err = lex.next_token().unwrap_err();
+        assert_eq!(err.kind, LexErrorKind::UnterminatedString);
+    }
+
+    #[test]
+    fn lex_whole_input() {
+        let (tokens, errors) = super::lex("let x = 5;");
+
+        assert!(errors.is_empty());
+        let kinds: Vec<Token> = tokens.into_iter().map(|(tok, _)| tok).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Token::Let,
+                Token::Ident("x"),
+                Token::Equal,
+                Token::Int("5"),
+                Token::Semicolon,
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn lexes_logical_and_comparison_operators() -> Result<()> {
+        let mut lex = Lexer::new("! a && b || c == d != e");
+
+        let tokens = vec![
+            Token::Bang,
+            Token::Ident("a"),
+            Token::AmpersandAmpersand,
+            Token::Ident("b"),
+            Token::PipePipe,
+            Token::Ident("c"),
+            Token::EqualEqual,
+            Token::Ident("d"),
+            Token::BangEqual,
+            Token::Ident("e"),
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            let (next_token, _) = lex.next_token()?;
+            assert_eq!(token, next_token);
+        }
+
+        return Ok(());
+    }
+
+    #[test]
+    fn single_ampersand_pipe_equal_are_distinct_from_their_doubled_form() -> Result<()> {
+        let mut lex = Lexer::new("& | =");
+
+        let tokens = vec![Token::Ampersand, Token::Pipe, Token::Equal, Token::Eof];
+
+        for token in tokens {
+            let (next_token, _) = lex.next_token()?;
+            assert_eq!(token, next_token);
+        }
+
+        return Ok(());
+    }
+
+    #[test]
+    fn lex_collects_every_error_instead_of_stopping_at_the_first() {
+        let (tokens, errors) = super::lex("let x = @ 1; let y = # 2;");
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].byte, b'@');
+        assert_eq!(errors[1].byte, b'#');
+
+        // scanning continued past both bad bytes and reached the real end
+        assert_eq!(tokens.last().unwrap().0, Token::Eof);
+    }
+
+    #[test]
+    fn lexer_as_iterator() {
+        let input = "let x = 5;";
+
+        let tokens: Vec<Token> = Lexer::new(input).map(|item| item.unwrap().0).collect();
+
+        assert_eq!(
+            tokens,
+            vec![Token::Let, Token::Ident("x"), Token::Equal, Token::Int("5"), Token::Semicolon,]
+        );
+
+        let mut peekable = Lexer::new(input).peekable();
+        assert_eq!(peekable.peek().unwrap().as_ref().unwrap().0, Token::Let);
+        assert_eq!(peekable.next().unwrap().unwrap().0, Token::Let);
+    }
+
+    #[test]
+    fn lexer_iterator_yields_errors_instead_of_swallowing_them() {
+        use super::LexErrorKind;
+
+        let mut lex = Lexer::new("x @ y");
+
+        assert_eq!(lex.next().unwrap().unwrap().0, Token::Ident("x"));
+        assert_eq!(lex.next().unwrap().unwrap_err().kind, LexErrorKind::UnexpectedChar);
+        assert_eq!(lex.next().unwrap().unwrap().0, Token::Ident("y"));
+        assert!(lex.next().is_none());
+    }
+
+    #[test]
+    fn operator_metadata_table() {
+        assert_eq!(Token::Plus.precedence(), Some(6));
+        assert_eq!(Token::Plus.assign_variant(), Some(Token::PlusEqual));
+        assert!(Token::Plus.is_binary_op());
+
+        assert_eq!(Token::Bang.precedence(), None);
+        assert_eq!(Token::Bang.assign_variant(), None);
+        assert!(!Token::Bang.is_binary_op());
+
+        assert_eq!(Token::Ident("x").precedence(), None);
+    }
+
+    #[test]
+    fn compound_assign_operators_lex_via_the_metadata_table() -> Result<()> {
+        let mut lex = Lexer::new("x += 1; y -= 2;");
+
+        let tokens = vec![
+            Token::Ident("x"),
+            Token::PlusEqual,
+            Token::Int("1"),
+            Token::Semicolon,
+            Token::Ident("y"),
+            Token::MinusEqual,
+            Token::Int("2"),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+
+        for token in tokens {
+            let (next_token, _) = lex.next_token()?;
+            assert_eq!(token, next_token);
+        }
+
+        return Ok(());
+    }
 }
@@ -0,0 +1,372 @@
+use std::iter::Peekable;
+
+use crate::lexer::lexer::{LexError, Lexer, Span, Token};
+
+#[derive(Debug, PartialEq)]
+pub enum Expr<'src> {
+    Int(&'src str),
+    Ident(&'src str),
+    Prefix {
+        op: Token<'src>,
+        rhs: Box<Expr<'src>>,
+    },
+    Infix {
+        lhs: Box<Expr<'src>>,
+        op: Token<'src>,
+        rhs: Box<Expr<'src>>,
+    },
+    Call {
+        callee: Box<Expr<'src>>,
+        args: Vec<Expr<'src>>,
+    },
+    Grouped(Box<Expr<'src>>),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Stmt<'src> {
+    Let { name: &'src str, value: Expr<'src> },
+    Return(Expr<'src>),
+    ExprStmt(Expr<'src>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Program<'src> {
+    pub statements: Vec<Stmt<'src>>,
+}
+
+/// A problem encountered while parsing, carrying the unexpected token and
+/// where it occurred so the caller can report it against the source.
+#[derive(Debug, PartialEq)]
+pub enum ParseError<'src> {
+    UnexpectedToken { found: Token<'src>, span: Span },
+    UnexpectedEof,
+    LexError(LexError<'src>),
+}
+
+/// Binding power used when recursing into a prefix (`!`/`-`) operand.
+/// Binds tighter than every infix operator but looser than the postfix
+/// `.`/`::`/call forms, so `-a.b` parses as `-(a.b)` and `-a + b` parses
+/// as `(-a) + b`.
+const PREFIX_BP: u8 = 7;
+
+pub struct Parser<'src> {
+    tokens: Peekable<Lexer<'src>>,
+    cur: (Token<'src>, Span),
+    lex_error: Option<LexError<'src>>,
+}
+
+impl<'src> Parser<'src> {
+    pub fn new(input: &'src str) -> Self {
+        let mut tokens = Lexer::new(input).peekable();
+        let mut lex_error = None;
+        let cur = Self::pull(&mut tokens, &mut lex_error, 0);
+
+        return Parser { tokens, cur, lex_error };
+    }
+
+    pub fn parse_program(&mut self) -> Result<Program<'src>, ParseError<'src>> {
+        let mut statements = Vec::new();
+
+        while self.cur.0 != Token::Eof {
+            statements.push(self.parse_stmt()?);
+        }
+
+        if let Some(err) = self.lex_error.take() {
+            return Err(ParseError::LexError(err));
+        }
+
+        return Ok(Program { statements });
+    }
+
+    /// Pulls the next token out of `tokens`, stashing (rather than raising)
+    /// any [`LexError`] and substituting `Token::Eof` in its place so the
+    /// rest of the parser can keep treating "ran out of input" and "the
+    /// lexer choked" the same way until `parse_program` checks
+    /// `lex_error` once parsing stops.
+    fn pull(
+        tokens: &mut Peekable<Lexer<'src>>,
+        lex_error: &mut Option<LexError<'src>>,
+        fallback_end: usize,
+    ) -> (Token<'src>, Span) {
+        return match tokens.next() {
+            Some(Ok(item)) => item,
+            Some(Err(err)) => {
+                let span = err.span;
+                *lex_error = Some(err);
+                (Token::Eof, span)
+            }
+            None => (Token::Eof, Span { start: fallback_end, end: fallback_end }),
+        };
+    }
+
+    fn advance(&mut self) -> (Token<'src>, Span) {
+        let end = self.cur.1.end;
+        let next = Self::pull(&mut self.tokens, &mut self.lex_error, end);
+
+        return std::mem::replace(&mut self.cur, next);
+    }
+
+    /// Builds the error for an unexpected `found`/`span`, preferring a
+    /// stashed [`LexError`] when one is pending: a lex failure always
+    /// surfaces as `Eof` in `cur` (see `pull`), so without this check it
+    /// would get reported as a plain `UnexpectedToken { found: Eof, .. }`
+    /// wherever parsing happens to give up, masking the real problem.
+    fn unexpected(&mut self, found: Token<'src>, span: Span) -> ParseError<'src> {
+        if let Some(err) = self.lex_error.take() {
+            return ParseError::LexError(err);
+        }
+
+        return ParseError::UnexpectedToken { found, span };
+    }
+
+    fn expect(&mut self, expected: Token<'src>) -> Result<(), ParseError<'src>> {
+        if self.cur.0 == expected {
+            self.advance();
+            return Ok(());
+        }
+
+        let (found, span) = self.advance();
+        return Err(self.unexpected(found, span));
+    }
+
+    fn parse_stmt(&mut self) -> Result<Stmt<'src>, ParseError<'src>> {
+        return match self.cur.0 {
+            Token::Let => self.parse_let_stmt(),
+            Token::Return => self.parse_return_stmt(),
+            _ => self.parse_expr_stmt(),
+        };
+    }
+
+    fn parse_let_stmt(&mut self) -> Result<Stmt<'src>, ParseError<'src>> {
+        self.advance(); // consume `let`
+
+        let name = match self.advance() {
+            (Token::Ident(name), _) => name,
+            (found, span) => return Err(self.unexpected(found, span)),
+        };
+
+        self.expect(Token::Equal)?;
+        let value = self.parse_expr(0)?;
+
+        if self.cur.0 == Token::Semicolon {
+            self.advance();
+        }
+
+        return Ok(Stmt::Let { name, value });
+    }
+
+    fn parse_return_stmt(&mut self) -> Result<Stmt<'src>, ParseError<'src>> {
+        self.advance(); // consume `return`
+
+        let value = self.parse_expr(0)?;
+
+        if self.cur.0 == Token::Semicolon {
+            self.advance();
+        }
+
+        return Ok(Stmt::Return(value));
+    }
+
+    fn parse_expr_stmt(&mut self) -> Result<Stmt<'src>, ParseError<'src>> {
+        let expr = self.parse_expr(0)?;
+
+        if self.cur.0 == Token::Semicolon {
+            self.advance();
+        }
+
+        return Ok(Stmt::ExprStmt(expr));
+    }
+
+    /// Precedence-climbing expression parser: parse a prefix/atom, then
+    /// keep folding in infix operators whose precedence is greater than
+    /// `min_bp`, recursing with that operator's precedence as the new
+    /// floor.
+    fn parse_expr(&mut self, min_bp: u8) -> Result<Expr<'src>, ParseError<'src>> {
+        let mut lhs = self.parse_prefix()?;
+
+        loop {
+            let op_bp = self.cur.0.precedence().unwrap_or(0);
+            if op_bp <= min_bp {
+                break;
+            }
+
+            if self.cur.0 == Token::Lparen {
+                lhs = self.parse_call(lhs)?;
+                continue;
+            }
+
+            let (op, _) = self.advance();
+            let rhs = self.parse_expr(op_bp)?;
+            lhs = Expr::Infix {
+                lhs: Box::new(lhs),
+                op,
+                rhs: Box::new(rhs),
+            };
+        }
+
+        return Ok(lhs);
+    }
+
+    fn parse_prefix(&mut self) -> Result<Expr<'src>, ParseError<'src>> {
+        return match &self.cur.0 {
+            Token::Int(_) => match self.advance() {
+                (Token::Int(text), _) => Ok(Expr::Int(text)),
+                _ => unreachable!(),
+            },
+            Token::Ident(_) => match self.advance() {
+                (Token::Ident(name), _) => Ok(Expr::Ident(name)),
+                _ => unreachable!(),
+            },
+            Token::Bang | Token::Minus => {
+                let (op, _) = self.advance();
+                let rhs = self.parse_expr(PREFIX_BP)?;
+                Ok(Expr::Prefix { op, rhs: Box::new(rhs) })
+            }
+            Token::Lparen => {
+                self.advance();
+                let expr = self.parse_expr(0)?;
+                self.expect(Token::Rparen)?;
+                Ok(Expr::Grouped(Box::new(expr)))
+            }
+            _ => {
+                let (found, span) = self.advance();
+                Err(self.unexpected(found, span))
+            }
+        };
+    }
+
+    fn parse_call(&mut self, callee: Expr<'src>) -> Result<Expr<'src>, ParseError<'src>> {
+        self.advance(); // consume `(`
+
+        let mut args = Vec::new();
+        if self.cur.0 != Token::Rparen {
+            loop {
+                args.push(self.parse_expr(0)?);
+
+                if self.cur.0 != Token::Comma {
+                    break;
+                }
+                self.advance();
+            }
+        }
+
+        self.expect(Token::Rparen)?;
+
+        return Ok(Expr::Call {
+            callee: Box::new(callee),
+            args,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Expr, Parser, Stmt};
+
+    #[test]
+    fn parses_let_and_return_statements() {
+        let mut parser = Parser::new("let x = 1 + 2; return x;");
+        let program = parser.parse_program().unwrap();
+
+        assert_eq!(
+            program.statements,
+            vec![
+                Stmt::Let {
+                    name: "x",
+                    value: Expr::Infix {
+                        lhs: Box::new(Expr::Int("1")),
+                        op: super::Token::Plus,
+                        rhs: Box::new(Expr::Int("2")),
+                    },
+                },
+                Stmt::Return(Expr::Ident("x")),
+            ]
+        );
+    }
+
+    #[test]
+    fn prefix_binds_tighter_than_infix() {
+        let mut parser = Parser::new("-1 + 2;");
+        let program = parser.parse_program().unwrap();
+
+        assert_eq!(
+            program.statements,
+            vec![Stmt::ExprStmt(Expr::Infix {
+                lhs: Box::new(Expr::Prefix {
+                    op: super::Token::Minus,
+                    rhs: Box::new(Expr::Int("1")),
+                }),
+                op: super::Token::Plus,
+                rhs: Box::new(Expr::Int("2")),
+            })]
+        );
+    }
+
+    #[test]
+    fn parses_unary_bang_and_logical_operators_from_real_source() {
+        // Exercises the full Lexer -> Parser pipeline (rather than hand-built
+        // Token values) so a gap in the lexer's own scanning of these
+        // operators shows up here, not just in lexer-level tests.
+        let mut parser = Parser::new("!x; a == b; a && b;");
+        let program = parser.parse_program().unwrap();
+
+        assert_eq!(
+            program.statements,
+            vec![
+                Stmt::ExprStmt(Expr::Prefix {
+                    op: super::Token::Bang,
+                    rhs: Box::new(Expr::Ident("x")),
+                }),
+                Stmt::ExprStmt(Expr::Infix {
+                    lhs: Box::new(Expr::Ident("a")),
+                    op: super::Token::EqualEqual,
+                    rhs: Box::new(Expr::Ident("b")),
+                }),
+                Stmt::ExprStmt(Expr::Infix {
+                    lhs: Box::new(Expr::Ident("a")),
+                    op: super::Token::AmpersandAmpersand,
+                    rhs: Box::new(Expr::Ident("b")),
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_grouped_expr_and_call() {
+        let mut parser = Parser::new("add(1, (2 + 3));");
+        let program = parser.parse_program().unwrap();
+
+        assert_eq!(
+            program.statements,
+            vec![Stmt::ExprStmt(Expr::Call {
+                callee: Box::new(Expr::Ident("add")),
+                args: vec![
+                    Expr::Int("1"),
+                    Expr::Grouped(Box::new(Expr::Infix {
+                        lhs: Box::new(Expr::Int("2")),
+                        op: super::Token::Plus,
+                        rhs: Box::new(Expr::Int("3")),
+                    })),
+                ],
+            })]
+        );
+    }
+
+    #[test]
+    fn a_lex_error_is_reported_instead_of_silently_truncating_the_program() {
+        use super::ParseError;
+
+        let mut parser = Parser::new("let x = 1 @ 2; let y = 3;");
+
+        assert!(matches!(parser.parse_program(), Err(ParseError::LexError(_))));
+    }
+
+    #[test]
+    fn a_lex_error_before_any_expression_atom_is_not_masked_as_unexpected_eof() {
+        use super::ParseError;
+
+        let mut parser = Parser::new("let x = @ 1;");
+
+        assert!(matches!(parser.parse_program(), Err(ParseError::LexError(_))));
+    }
+}